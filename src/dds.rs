@@ -0,0 +1,141 @@
+//! Direct digital synthesis (DDS) tone generator.
+//!
+//! The FM patterns used to build square waves from an integer
+//! `half_period = 32 / (freq_factor / 2)`, which quantizes badly (period 2
+//! vs 3 is a huge pitch jump) and can never land on a real musical
+//! frequency at `SAMPLE_RATE`. [`PhaseOsc`] instead keeps a fractional `u32`
+//! phase accumulator, so the *average* output frequency is exact even
+//! though individual periods vary by a sample here and there. The same
+//! accumulator now also drives [`Waveform`]s beyond a 50%-duty square.
+
+/// High/low output levels matching the existing `0x8000`/`0x0000` I2S convention.
+pub const HIGH: u16 = 0x8000;
+pub const LOW: u16 = 0x0000;
+
+/// Single-cycle sine table, `i16` centered on zero, read by [`Waveform::Sine`].
+/// Kept local to this module rather than pulling in `oscillator`'s full
+/// wavetable engine (`Oscil` plus the saw/square/triangle/noise tables),
+/// which this FM-pattern generator never uses.
+const SINE_TABLE: [i16; 256] = [
+    0, 804, 1608, 2410, 3212, 4011, 4808, 5602,
+    6393, 7179, 7962, 8739, 9512, 10278, 11039, 11793,
+    12539, 13279, 14010, 14732, 15446, 16151, 16846, 17530,
+    18204, 18868, 19519, 20159, 20787, 21403, 22005, 22594,
+    23170, 23731, 24279, 24811, 25329, 25832, 26319, 26790,
+    27245, 27683, 28105, 28510, 28898, 29268, 29621, 29956,
+    30273, 30571, 30852, 31113, 31356, 31580, 31785, 31971,
+    32137, 32285, 32412, 32521, 32609, 32678, 32728, 32757,
+    32767, 32757, 32728, 32678, 32609, 32521, 32412, 32285,
+    32137, 31971, 31785, 31580, 31356, 31113, 30852, 30571,
+    30273, 29956, 29621, 29268, 28898, 28510, 28105, 27683,
+    27245, 26790, 26319, 25832, 25329, 24811, 24279, 23731,
+    23170, 22594, 22005, 21403, 20787, 20159, 19519, 18868,
+    18204, 17530, 16846, 16151, 15446, 14732, 14010, 13279,
+    12539, 11793, 11039, 10278, 9512, 8739, 7962, 7179,
+    6393, 5602, 4808, 4011, 3212, 2410, 1608, 804,
+    0, -804, -1608, -2410, -3212, -4011, -4808, -5602,
+    -6393, -7179, -7962, -8739, -9512, -10278, -11039, -11793,
+    -12539, -13279, -14010, -14732, -15446, -16151, -16846, -17530,
+    -18204, -18868, -19519, -20159, -20787, -21403, -22005, -22594,
+    -23170, -23731, -24279, -24811, -25329, -25832, -26319, -26790,
+    -27245, -27683, -28105, -28510, -28898, -29268, -29621, -29956,
+    -30273, -30571, -30852, -31113, -31356, -31580, -31785, -31971,
+    -32137, -32285, -32412, -32521, -32609, -32678, -32728, -32757,
+    -32767, -32757, -32728, -32678, -32609, -32521, -32412, -32285,
+    -32137, -31971, -31785, -31580, -31356, -31113, -30852, -30571,
+    -30273, -29956, -29621, -29268, -28898, -28510, -28105, -27683,
+    -27245, -26790, -26319, -25832, -25329, -24811, -24279, -23731,
+    -23170, -22594, -22005, -21403, -20787, -20159, -19519, -18868,
+    -18204, -17530, -16846, -16151, -15446, -14732, -14010, -13279,
+    -12539, -11793, -11039, -10278, -9512, -8739, -7962, -7179,
+    -6393, -5602, -4808, -4011, -3212, -2410, -1608, -804,
+];
+
+/// Waveform shape read out of the phase accumulator.
+#[derive(Clone, Copy)]
+pub enum Waveform {
+    /// 50%-duty square (the original FM-pattern behavior).
+    Square,
+    Sine,
+    Triangle,
+    Saw,
+    /// Variable-duty pulse, `duty_percent` in `0..=100`.
+    Pulse { duty_percent: u16 },
+}
+
+/// Phase-accumulator oscillator: square by default, switchable to any [`Waveform`].
+pub struct PhaseOsc {
+    phase: u32,
+    phase_inc: u32,
+    sample_rate: u32,
+    waveform: Waveform,
+}
+
+impl PhaseOsc {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            phase: 0,
+            phase_inc: 0,
+            sample_rate,
+            waveform: Waveform::Square,
+        }
+    }
+
+    /// Set the oscillator frequency in Hz.
+    pub fn set_freq(&mut self, freq_hz: f32) {
+        self.phase_inc = ((freq_hz as f64) * (1u64 << 32) as f64 / self.sample_rate as f64) as u32;
+    }
+
+    pub fn set_waveform(&mut self, waveform: Waveform) {
+        self.waveform = waveform;
+    }
+
+    /// Advance by one sample and return the waveform output, in the same
+    /// `0x0000..=0xFFFF` biased range as the rest of this crate's I2S buffers.
+    pub fn next_sample(&mut self) -> u16 {
+        self.phase = self.phase.wrapping_add(self.phase_inc);
+
+        match self.waveform {
+            Waveform::Square => {
+                if self.phase & 0x8000_0000 != 0 {
+                    HIGH
+                } else {
+                    LOW
+                }
+            }
+            Waveform::Sine => {
+                let index = (self.phase >> 24) as usize; // top 8 bits -> 0..256
+                Self::bias(SINE_TABLE[index])
+            }
+            Waveform::Triangle => {
+                let frac = self.phase as f32 / u32::MAX as f32;
+                let value = 1.0 - 4.0 * (frac - 0.5).abs();
+                Self::bias((value * 32767.0) as i16)
+            }
+            Waveform::Saw => {
+                let frac = self.phase as f32 / u32::MAX as f32;
+                let value = frac * 2.0 - 1.0;
+                Self::bias((value * 32767.0) as i16)
+            }
+            Waveform::Pulse { duty_percent } => {
+                let threshold = (duty_percent as u64 * u32::MAX as u64 / 100) as u32;
+                if self.phase < threshold {
+                    HIGH
+                } else {
+                    LOW
+                }
+            }
+        }
+    }
+
+    fn bias(signed_sample: i16) -> u16 {
+        (signed_sample as i32 + 32768) as u16
+    }
+
+    /// Fill `buf` with `buf.len()` samples at the current frequency/waveform.
+    pub fn fill(&mut self, buf: &mut [u16]) {
+        for sample in buf.iter_mut() {
+            *sample = self.next_sample();
+        }
+    }
+}
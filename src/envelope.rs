@@ -0,0 +1,82 @@
+//! Reusable ADSR amplitude envelope.
+//!
+//! Every FM pattern used to slam straight between `0x8000` and `0x0000`,
+//! which pops, and the AM pattern hand-rolled its own piecewise-linear ramp.
+//! [`Envelope`] is a single classic Attack/Decay/Sustain/Release state
+//! machine driven by a `gate`, so any pattern can shape its oscillator
+//! output into a plucked/keyed note instead of a raw gated square.
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Idle,
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+/// ADSR envelope with stage times given directly in samples.
+pub struct Envelope {
+    stage: Stage,
+    level: f32,
+    attack_samples: u32,
+    decay_samples: u32,
+    sustain_level: f32,
+    release_samples: u32,
+    release_rate: f32,
+}
+
+impl Envelope {
+    pub fn new(attack_samples: u32, decay_samples: u32, sustain_level: f32, release_samples: u32) -> Self {
+        Self {
+            stage: Stage::Idle,
+            level: 0.0,
+            attack_samples: attack_samples.max(1),
+            decay_samples: decay_samples.max(1),
+            sustain_level,
+            release_samples: release_samples.max(1),
+            release_rate: 0.0,
+        }
+    }
+
+    /// Gate the envelope on (retrigger Attack) or off (jump to Release,
+    /// ramping from whatever level the envelope is currently at).
+    pub fn gate(&mut self, on: bool) {
+        if on {
+            self.stage = Stage::Attack;
+        } else {
+            self.release_rate = self.level / self.release_samples as f32;
+            self.stage = Stage::Release;
+        }
+    }
+
+    /// Advance the envelope by one sample and return the level, `0.0..=1.0`.
+    pub fn next_level(&mut self) -> f32 {
+        match self.stage {
+            Stage::Idle => {}
+            Stage::Attack => {
+                self.level += 1.0 / self.attack_samples as f32;
+                if self.level >= 1.0 {
+                    self.level = 1.0;
+                    self.stage = Stage::Decay;
+                }
+            }
+            Stage::Decay => {
+                self.level -= (1.0 - self.sustain_level) / self.decay_samples as f32;
+                if self.level <= self.sustain_level {
+                    self.level = self.sustain_level;
+                    self.stage = Stage::Sustain;
+                }
+            }
+            Stage::Sustain => {}
+            Stage::Release => {
+                self.level -= self.release_rate;
+                if self.level <= 0.0 {
+                    self.level = 0.0;
+                    self.stage = Stage::Idle;
+                }
+            }
+        }
+        self.level
+    }
+}
@@ -0,0 +1,110 @@
+//! Audio-reactive input path: a bank of band-pass biquads spread across the
+//! spectrum, used to find the dominant band (and beats) in whatever is
+//! captured on the I2S RX line.
+
+/// Number of bands in the filter bank.
+pub const NUM_BANDS: usize = 8;
+
+/// Exponential decay applied to each band's energy baseline, per window.
+const BASELINE_DECAY: f32 = 0.98;
+
+/// A single band-pass biquad, Direct Form I, high-Q per Audio EQ Cookbook.
+struct BandPassFilter {
+    b0: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl BandPassFilter {
+    fn new(center_hz: f32, q: f32, sample_rate: u32) -> Self {
+        let w0 = 2.0 * core::f32::consts::PI * center_hz / sample_rate as f32;
+        let (sin_w0, cos_w0) = (libm::sinf(w0), libm::cosf(w0));
+        let alpha = sin_w0 / (2.0 * q);
+
+        let a0 = 1.0 + alpha;
+        Self {
+            b0: alpha / a0,
+            b2: -alpha / a0,
+            a1: (-2.0 * cos_w0) / a0,
+            a2: (1.0 - alpha) / a0,
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    fn process(&mut self, x: i16) -> f32 {
+        let x0 = x as f32;
+        // b1 == 0 for this band-pass form, so it drops out of the recurrence.
+        let y = self.b0 * x0 + self.b2 * self.x2 - self.a1 * self.y1 - self.a2 * self.y2;
+
+        self.x2 = self.x1;
+        self.x1 = x0;
+        self.y2 = self.y1;
+        self.y1 = y;
+
+        y
+    }
+}
+
+/// A bank of log-spaced band-pass filters used to detect the dominant band
+/// (and, by extension, beats) in a captured audio signal.
+pub struct FilterBank {
+    filters: [BandPassFilter; NUM_BANDS],
+    energy: [f32; NUM_BANDS],
+    baseline: [f32; NUM_BANDS],
+}
+
+impl FilterBank {
+    /// Build a bank of `NUM_BANDS` filters log-spaced between `low_hz` and `high_hz`.
+    pub fn new(low_hz: f32, high_hz: f32, q: f32, sample_rate: u32) -> Self {
+        let log_low = libm::logf(low_hz);
+        let log_high = libm::logf(high_hz);
+        let step = (log_high - log_low) / (NUM_BANDS - 1) as f32;
+
+        let filters = core::array::from_fn(|i| {
+            let center_hz = libm::expf(log_low + step * i as f32);
+            BandPassFilter::new(center_hz, q, sample_rate)
+        });
+
+        Self {
+            filters,
+            energy: [0.0; NUM_BANDS],
+            baseline: [0.0; NUM_BANDS],
+        }
+    }
+
+    /// Run a block of captured samples through every band, accumulating
+    /// per-band energy, and return the index of the band whose energy most
+    /// exceeds its slowly-decaying baseline (the "active"/beat band).
+    pub fn process(&mut self, samples: &[i16]) -> usize {
+        self.energy = [0.0; NUM_BANDS];
+
+        for &sample in samples {
+            for band in 0..NUM_BANDS {
+                let y = self.filters[band].process(sample);
+                self.energy[band] += y * y;
+            }
+        }
+
+        let mut active_band = 0;
+        let mut best_margin = f32::MIN;
+        for band in 0..NUM_BANDS {
+            let margin = self.energy[band] - self.baseline[band];
+            if margin > best_margin {
+                best_margin = margin;
+                active_band = band;
+            }
+            self.baseline[band] = self.baseline[band] * BASELINE_DECAY
+                + self.energy[band] * (1.0 - BASELINE_DECAY);
+        }
+
+        active_band
+    }
+}
@@ -11,7 +11,7 @@ use esp_hal::{
     dma::{Dma, DmaPriority},
     dma_buffers,
     gpio::{Io, Level, Output},
-    i2s::{DataFormat, I2s, I2sWrite, Standard},
+    i2s::{DataFormat, I2s, Standard},
     peripherals::Peripherals,
     prelude::*,
     system::SystemControl,
@@ -20,7 +20,28 @@ use esp_println::println;
 use esp_backtrace as _;
 use esp_hal::entry;
 
+mod dds;
+mod envelope;
+mod morse;
+mod noise;
+mod sequencer;
+mod stream;
+use dds::{PhaseOsc, Waveform};
+use envelope::Envelope;
+use morse::{Event, Keyer};
+use noise::Noise;
+use sequencer::Sequencer;
+use stream::StreamWriter;
+
 const SAMPLE_RATE: u32 = 16000; // Higher sample rate for better FM resolution
+
+/// Convert the old `freq_factor` unit (half-cycles per 32-sample buffer) into
+/// a real Hz value for [`PhaseOsc`]: `factor` half-cycles every 32 samples at
+/// `SAMPLE_RATE` is `SAMPLE_RATE * factor / 64` full cycles per second.
+fn factor_to_hz(freq_factor: f32) -> f32 {
+    SAMPLE_RATE as f32 * freq_factor / 64.0
+}
+
 const I2S_DATA_FORMAT: DataFormat = DataFormat::Data16Channel16;
 const I2S_STANDARD: Standard = Standard::Philips;
 
@@ -28,11 +49,16 @@ const I2S_STANDARD: Standard = Standard::Philips;
 const TX_BUFFER_SIZE: usize = 512;
 const RX_BUFFER_SIZE: usize = 256;
 
+/// Samples per half of the circular streaming buffer (see [`stream`]).
+/// `TX_BUFFER_SIZE` is a *byte* count (as `dma_buffers!` expects), and each
+/// half holds `u16` samples, so this is `TX_BUFFER_SIZE / 2 / 2`.
+const HALF_LEN: usize = TX_BUFFER_SIZE / 4;
+
 #[entry]
 fn main() -> ! {
     println!("📻 ESP32-H2 I2S FM-STYLE PATTERN GENERATOR 📻");
     println!("🎵 Creates FM-like patterns using digital square waves!");
-    
+
     let peripherals = Peripherals::take();
     let system = SystemControl::new(peripherals.SYSTEM);
     let clocks = ClockControl::boot_defaults(system.clock_control).freeze();
@@ -56,7 +82,7 @@ fn main() -> ! {
     let dma = Dma::new(peripherals.DMA);
     let dma_channel = dma.channel0.configure(false, DmaPriority::Priority0);
 
-    let (_rx_buffer, rx_descriptors, _tx_buffer, tx_descriptors) = 
+    let (_rx_buffer, rx_descriptors, tx_buffer, tx_descriptors) =
         dma_buffers!(RX_BUFFER_SIZE, TX_BUFFER_SIZE);
 
     // Create I2S instance
@@ -75,16 +101,21 @@ fn main() -> ! {
     let bclk = io.pins.gpio4;
     let ws = io.pins.gpio5;
     let dout = io.pins.gpio12;
-    
-    let mut i2s_tx = i2s.i2s_tx
+
+    let i2s_tx = i2s.i2s_tx
         .with_bclk(bclk)
         .with_ws(ws)
         .with_dout(dout)
         .build();
-    
+
+    // Keep the I2S clock running continuously: a persistent circular DMA
+    // transfer over `tx_buffer`, refilled half-by-half as the hardware
+    // drains it, instead of blocking write()+delay() calls with dead gaps.
+    let mut stream = StreamWriter::<_, HALF_LEN>::start(i2s_tx, tx_buffer);
+
     println!("✅ I2S TX Configuration Complete:");
     println!("   🔌 BCLK: GPIO4 (Bit Clock)");
-    println!("   🔌 WS:   GPIO5 (Word Select/Frame Sync)"); 
+    println!("   🔌 WS:   GPIO5 (Word Select/Frame Sync)");
     println!("   🔌 DOUT: GPIO12 (FM-Style Data Out)");
     println!("   📊 Sample Rate: {} Hz", SAMPLE_RATE);
     println!("   🎼 Format: 16-bit, Philips I2S");
@@ -104,370 +135,402 @@ fn main() -> ! {
     println!("   📊 Step Frequency (Digital frequency steps)");
     println!("   💫 Chirp Signal (Quick frequency sweep)");
     println!("   🎶 Musical Scale (Note progression)");
-    println!("   📢 SOS Morse in FM (Emergency signal)");
+    println!("   📢 Morse Code (CW keyer, any text)");
+    println!("   🎛️ PWM Sweep (Variable-duty pulse wave)");
+    println!("   🥁 Noise Bursts (LFSR snare/hi-hat hits)");
     println!();
 
     let mut cycle_count = 0;
 
     loop {
         cycle_count += 1;
-        
+
         println!("📻 === FM PATTERN CYCLE #{} === 📻", cycle_count);
-        
+
         // FM Pattern 1: Frequency Sweep (Low to High to Low)
         {
             led.set_high();
-            println!("🎵 FM Pattern 1/7: Frequency Sweep");
-            println!("   📻 Frequency gradually increases then decreases");
-            
-            // Create frequency sweep using varying square wave patterns
-            for sweep in 0..40 {
-                let mut pattern = [0u16; 32];
-                
-                // Calculate frequency: low at start/end, high in middle
-                let freq_factor = if sweep < 20 {
-                    sweep + 1  // Increasing frequency
-                } else {
-                    41 - sweep // Decreasing frequency
-                };
-                
-                // Create square wave with varying frequency
-                let half_period = 32 / (freq_factor / 2).max(1);
-                for i in 0..32 {
-                    pattern[i] = if (i / half_period) % 2 == 0 {
-                        0x8000  // High
-                    } else {
-                        0x0000  // Low
-                    };
-                }
-                
-                match i2s_tx.write(&pattern) {
-                    Ok(_) => {
-                        if sweep % 10 == 0 {
-                            println!("   📊 Sweep progress: {}%", (sweep * 100) / 40);
-                        }
-                    }
-                    Err(e) => {
-                        println!("   ❌ Error: {:?}", e);
-                        break;
+            println!("🎵 FM Pattern 1/9: Frequency Sweep");
+            println!("   📻 Frequency gradually increases then decreases, sample-accurately");
+
+            // 2s rising + 2s falling, continuously streamed: the DDS phase
+            // accumulator is retuned every sample instead of every 32-sample
+            // block, so there's no 50ms step in the frequency ramp anymore.
+            let mut sweep_osc = PhaseOsc::new(SAMPLE_RATE);
+            sweep_osc.set_waveform(Waveform::Triangle);
+            let ramp_samples = (SAMPLE_RATE * 2) as usize;
+            let total_samples = ramp_samples * 2;
+            let refills = total_samples / HALF_LEN;
+            let mut elapsed = 0usize;
+
+            for refill in 0..refills {
+                stream.fill_with(|half| {
+                    for sample in half.iter_mut() {
+                        let t = if elapsed < ramp_samples {
+                            elapsed as f32 / ramp_samples as f32
+                        } else {
+                            1.0 - (elapsed - ramp_samples) as f32 / ramp_samples as f32
+                        };
+                        sweep_osc.set_freq(40.0 + 960.0 * t);
+                        *sample = sweep_osc.next_sample();
+                        elapsed += 1;
                     }
+                });
+                if refill % (refills / 4).max(1) == 0 {
+                    println!("   📊 Sweep progress: {}%", (refill * 100) / refills.max(1));
                 }
-                delay.delay_millis(50);
             }
-            
+
             led.set_low();
             println!("   ✅ Frequency sweep complete");
-            delay.delay_millis(300);
         }
-        
+
         // FM Pattern 2: AM-like Modulation (Amplitude Bursts)
         {
             led.set_high();
-            println!("🎵 FM Pattern 2/7: AM-like Amplitude Modulation");
+            println!("🎵 FM Pattern 2/9: AM-like Amplitude Modulation");
             println!("   📡 Square wave with varying amplitude envelopes");
-            
-            for burst in 0..20 {
-                let mut pattern = [0u16; 32];
-                
-                // Create envelope: amplitude varies in a wave pattern
-                let envelope = if burst < 5 {
-                    (burst as f32) / 5.0  // Rising
-                } else if burst < 15 {
-                    1.0  // Peak
-                } else {
-                    (20 - burst) as f32 / 5.0  // Falling
-                };
-                let amplitude = (envelope * 32767.0) as u16;
-                
-                // Create square wave with modulated amplitude
-                for i in 0..32 {
-                    pattern[i] = if i % 4 < 2 {
-                        amplitude  // High with envelope
-                    } else {
-                        0x0000     // Low
-                    };
-                }
-                
-                match i2s_tx.write(&pattern) {
-                    Ok(_) => {
-                        if burst % 5 == 0 {
-                            println!("   📊 AM burst: {}/20 (envelope: {:.1}%)", burst + 1, envelope * 100.0);
+
+            // Envelope-shaped amplitude instead of a hand-rolled piecewise ramp:
+            // Attack over 3/4 of the span before release (9/16 of the burst),
+            // hold at full level, then Release over the last quarter, all in
+            // exact sample counts.
+            let total_samples = 20 * 32;
+            let release_at = (total_samples * 3) / 4;
+            let mut am_envelope = Envelope::new((release_at * 3 / 4) as u32, 1, 1.0, (total_samples - release_at) as u32);
+            let mut am_osc = PhaseOsc::new(SAMPLE_RATE);
+            am_osc.set_freq(4000.0);
+            am_envelope.gate(true);
+
+            let refills = total_samples / HALF_LEN;
+            let mut elapsed = 0usize;
+            let mut last_level = 0.0;
+
+            for refill in 0..refills {
+                stream.fill_with(|half| {
+                    for sample in half.iter_mut() {
+                        if elapsed == release_at {
+                            am_envelope.gate(false);
                         }
+                        last_level = am_envelope.next_level();
+                        *sample = if am_osc.next_sample() == dds::HIGH {
+                            (last_level * 32767.0) as u16
+                        } else {
+                            0x0000
+                        };
+                        elapsed += 1;
                     }
-                    Err(e) => {
-                        println!("   ❌ Error: {:?}", e);
-                        break;
-                    }
+                });
+                if refill % (refills / 4).max(1) == 0 {
+                    println!("   📊 AM progress: envelope {:.1}%", last_level * 100.0);
                 }
-                delay.delay_millis(75);
             }
-            
+
             led.set_low();
             println!("   ✅ AM modulation complete");
-            delay.delay_millis(300);
         }
-        
+
         // FM Pattern 3: Frequency Wobble (Back and Forth)
         {
             led.set_high();
-            println!("🎵 FM Pattern 3/7: Frequency Wobble");
+            println!("🎵 FM Pattern 3/9: Frequency Wobble");
             println!("   🌊 Frequency oscillates back and forth");
-            
-            for wobble in 0..30 {
-                let mut pattern = [0u16; 32];
-                
-                // Create wobbling frequency (triangle wave frequency modulation)
-                let wobble_factor = if wobble < 8 {
-                    3 + wobble  // Rising frequency
-                } else if wobble < 23 {
-                    11  // Peak frequency
-                } else {
-                    33 - wobble  // Falling frequency
-                } as usize;
-                let period = (32 / wobble_factor).max(2);
-                
-                for i in 0..32 {
-                    pattern[i] = if (i / period) % 2 == 0 {
-                        0x8000  // High
-                    } else {
-                        0x0000  // Low
-                    };
-                }
-                
-                match i2s_tx.write(&pattern) {
-                    Ok(_) => {
-                        if wobble % 8 == 0 {
-                            println!("   🌊 Wobble cycle: {}/30", wobble + 1);
-                        }
-                    }
-                    Err(e) => {
-                        println!("   ❌ Error: {:?}", e);
-                        break;
+
+            let mut wobble_osc = PhaseOsc::new(SAMPLE_RATE);
+            let total_samples = (SAMPLE_RATE * 2) as usize; // ~1.8s scaled to a round 2s
+            let rise_end = total_samples / 4;
+            let plateau_end = (total_samples * 3) / 4;
+            let refills = total_samples / HALF_LEN;
+            let mut elapsed = 0usize;
+
+            for refill in 0..refills {
+                stream.fill_with(|half| {
+                    for sample in half.iter_mut() {
+                        let freq_factor = if elapsed < rise_end {
+                            3.0 + 8.0 * (elapsed as f32 / rise_end as f32)
+                        } else if elapsed < plateau_end {
+                            11.0
+                        } else {
+                            11.0 - 8.0 * ((elapsed - plateau_end) as f32 / (total_samples - plateau_end) as f32)
+                        };
+                        wobble_osc.set_freq(factor_to_hz(freq_factor));
+                        *sample = wobble_osc.next_sample();
+                        elapsed += 1;
                     }
+                });
+                if refill % (refills / 4).max(1) == 0 {
+                    println!("   🌊 Wobble progress: {}%", (refill * 100) / refills.max(1));
                 }
-                delay.delay_millis(60);
             }
-            
+
             led.set_low();
             println!("   ✅ Frequency wobble complete");
-            delay.delay_millis(300);
         }
-        
-        // FM Pattern 4: Step Frequency (Digital Steps)
+
+        // FM Pattern 4: Step Frequency (Digital Steps), driven by the
+        // Sequencer engine instead of a hardcoded `frequencies` array + loop.
         {
             led.set_high();
-            println!("🎵 FM Pattern 4/7: Step Frequency Changes");
-            println!("   📊 Discrete frequency steps (digital tuning)");
-            
-            let frequencies = [2, 4, 6, 8, 12, 16, 8, 4]; // Different step frequencies
-            
-            for (step, &freq) in frequencies.iter().enumerate() {
-                let mut pattern = [0u16; 32];
-                let period = (32 / freq).max(1);
-                
-                for i in 0..32 {
-                    pattern[i] = if (i / period) % 2 == 0 {
-                        0x8000  // High
-                    } else {
-                        0x0000  // Low
-                    };
-                }
-                
-                println!("   📻 Step {}: Frequency {} (period {})", step + 1, freq, period);
-                
-                // Repeat each frequency step multiple times
-                for repeat in 0..8 {
-                    match i2s_tx.write(&pattern) {
-                        Ok(_) => {},
-                        Err(e) => {
-                            println!("   ❌ Error: {:?}", e);
-                            break;
-                        }
+            println!("🎵 FM Pattern 4/9: Step Frequency Changes");
+            println!("   📊 Discrete frequency steps, played from the sequencer::STEP_TUNE table");
+
+            let mut seq = Sequencer::new(sequencer::STEP_TUNE, SAMPLE_RATE, 8, 200);
+            while !seq.is_finished() {
+                stream.fill_with(|half| {
+                    for sample in half.iter_mut() {
+                        *sample = seq.next_sample().unwrap_or(0x0000);
                     }
-                    delay.delay_millis(40);
-                }
-                
-                delay.delay_millis(100); // Pause between steps
+                });
             }
-            
+
             led.set_low();
             println!("   ✅ Step frequency complete");
-            delay.delay_millis(300);
         }
-        
+
         // FM Pattern 5: Chirp Signal (Quick Frequency Sweep)
         {
             led.set_high();
-            println!("🎵 FM Pattern 5/7: Chirp Signal");
+            println!("🎵 FM Pattern 5/9: Chirp Signal");
             println!("   💫 Rapid frequency sweep (radar-like chirp)");
-            
+
+            let mut chirp_osc = PhaseOsc::new(SAMPLE_RATE);
+            chirp_osc.set_waveform(Waveform::Saw);
+            let chirp_samples = (SAMPLE_RATE / 3) as usize; // ~0.33s per chirp
+
             for chirp in 0..3 { // 3 chirp cycles
                 println!("   💫 Chirp {}/3", chirp + 1);
-                
-                // Quick frequency sweep from low to high
-                for freq_step in 1..=16 {
-                    let mut pattern = [0u16; 32];
-                    let period = (32 / freq_step).max(1);
-                    
-                    for i in 0..32 {
-                        pattern[i] = if (i / period) % 2 == 0 {
-                            0x8000  // High
-                        } else {
-                            0x0000  // Low
-                        };
-                    }
-                    
-                    match i2s_tx.write(&pattern) {
-                        Ok(_) => {},
-                        Err(e) => {
-                            println!("   ❌ Error: {:?}", e);
-                            break;
+
+                let refills = chirp_samples / HALF_LEN;
+                let mut elapsed = 0usize;
+                for _ in 0..refills {
+                    stream.fill_with(|half| {
+                        for sample in half.iter_mut() {
+                            let freq_factor = 1.0 + 15.0 * (elapsed as f32 / chirp_samples as f32);
+                            chirp_osc.set_freq(factor_to_hz(freq_factor));
+                            *sample = chirp_osc.next_sample();
+                            elapsed += 1;
                         }
-                    }
-                    delay.delay_millis(20); // Quick sweep
+                    });
                 }
-                
-                delay.delay_millis(200); // Pause between chirps
             }
-            
+
             led.set_low();
             println!("   ✅ Chirp signal complete");
-            delay.delay_millis(300);
         }
-        
-        // FM Pattern 6: Musical Scale (Note Progression)
+
+        // FM Pattern 6: Musical Scale (Note Progression), driven by the
+        // Sequencer engine from the sequencer::MAJOR_SCALE event table —
+        // adding a new tune is now a data change, not nested loops.
         {
             led.set_high();
-            println!("🎵 FM Pattern 6/7: Musical Scale");
-            println!("   🎶 Frequency steps mimicking musical notes");
-            
-            // Musical scale frequencies (simplified as periods)
-            let notes = [16, 14, 12, 11, 10, 9, 8, 7]; // Descending scale
-            let note_names = ["C", "D", "E", "F", "G", "A", "B", "C"];
-            
-            for (note_idx, &note_period) in notes.iter().enumerate() {
-                let mut pattern = [0u16; 32];
-                
-                for i in 0..32 {
-                    pattern[i] = if (i / note_period) % 2 == 0 {
-                        0x8000  // High
-                    } else {
-                        0x0000  // Low
-                    };
-                }
-                
-                println!("   🎵 Note {}: {} (period {})", note_idx + 1, note_names[note_idx], note_period);
-                
-                // Play each note
-                for repeat in 0..6 {
-                    match i2s_tx.write(&pattern) {
-                        Ok(_) => {},
-                        Err(e) => {
-                            println!("   ❌ Error: {:?}", e);
-                            break;
-                        }
+            println!("🎵 FM Pattern 6/9: Musical Scale");
+            println!("   🎶 C major scale, played from the sequencer::MAJOR_SCALE table");
+
+            // Short attack/release per note so each one is plucked, not gated.
+            let mut seq = Sequencer::new(sequencer::MAJOR_SCALE, SAMPLE_RATE, 20, 60);
+            while !seq.is_finished() {
+                stream.fill_with(|half| {
+                    for sample in half.iter_mut() {
+                        *sample = seq.next_sample().unwrap_or(0x0000);
                     }
-                    delay.delay_millis(80);
-                }
-                
-                delay.delay_millis(50); // Brief pause between notes
+                });
             }
-            
+
             led.set_low();
             println!("   ✅ Musical scale complete");
-            delay.delay_millis(300);
         }
-        
-        // FM Pattern 7: SOS Morse in FM
+
+        // FM Pattern 7: Morse/CW Keyer
         {
             led.set_high();
-            println!("🎵 FM Pattern 7/7: SOS Morse Code in FM");
-            println!("   📢 Emergency signal using frequency modulation");
-            
-            // SOS: ... --- ... (3 dots, 3 dashes, 3 dots)
-            let sos_pattern = [
-                (8, 4),   // S: dot (high freq, short)
-                (8, 4),   // S: dot  
-                (8, 4),   // S: dot
-                (0, 8),   // Gap
-                (4, 12),  // O: dash (low freq, long)
-                (4, 12),  // O: dash
-                (4, 12),  // O: dash  
-                (0, 8),   // Gap
-                (8, 4),   // S: dot
-                (8, 4),   // S: dot
-                (8, 4),   // S: dot
-            ];
-            
-            for sos_cycle in 0..2 {
-                println!("   📢 SOS transmission {}/2", sos_cycle + 1);
-                
-                for (freq, duration) in sos_pattern.iter() {
-                    if *freq == 0 {
-                        // Silence (gap)
-                        let silence = [0u16; 32];
-                        for _ in 0..*duration {
-                            match i2s_tx.write(&silence) {
-                                Ok(_) => {},
-                                Err(e) => {
-                                    println!("   ❌ Error: {:?}", e);
-                                    break;
-                                }
+            println!("🎵 FM Pattern 7/9: Morse Code (CW Keyer)");
+            println!("   📢 Arbitrary text keyed as Morse via the CW keyer module");
+
+            // "SOS" is just one message among any the `morse` table can spell;
+            // unit length comes straight from WPM instead of hand-picked sample counts.
+            let message = "SOS SOS";
+            let wpm = 15;
+            let unit_samples = Keyer::unit_samples(wpm, SAMPLE_RATE) as usize;
+            let sidetone_hz = 700.0;
+
+            let mut morse_osc = PhaseOsc::new(SAMPLE_RATE);
+            morse_osc.set_freq(sidetone_hz);
+
+            for cycle in 0..2 {
+                println!("   📢 Keying \"{}\" at {} WPM ({}/2)", message, wpm, cycle + 1);
+                let mut keyer = Keyer::new(message);
+
+                while let Some(event) = keyer.next_event() {
+                    match event {
+                        Event::KeyDown(units) => {
+                            // Shaped by a keyed envelope (attack on key-down,
+                            // release just before key-up) for clean edges.
+                            let elem_samples = units as usize * unit_samples;
+                            let mut morse_envelope = Envelope::new(16, 1, 1.0, 32);
+                            morse_envelope.gate(true);
+                            let release_at = elem_samples.saturating_sub(32);
+
+                            let refills = elem_samples / HALF_LEN;
+                            let mut elapsed = 0usize;
+                            for _ in 0..refills {
+                                stream.fill_with(|half| {
+                                    for sample in half.iter_mut() {
+                                        if elapsed == release_at {
+                                            morse_envelope.gate(false);
+                                        }
+                                        let level = morse_envelope.next_level();
+                                        *sample = if morse_osc.next_sample() == dds::HIGH {
+                                            (level * 32767.0) as u16
+                                        } else {
+                                            0x0000
+                                        };
+                                        elapsed += 1;
+                                    }
+                                });
                             }
-                            delay.delay_millis(50);
                         }
-                    } else {
-                        // Tone with specific frequency
-                        let mut pattern = [0u16; 32];
-                        let period = 32 / freq;
-                        
-                        for i in 0..32 {
-                            pattern[i] = if (i / period) % 2 == 0 {
-                                0x8000  // High
-                            } else {
-                                0x0000  // Low
-                            };
+                        Event::KeyUp(units) => {
+                            let elem_samples = units as usize * unit_samples;
+                            let refills = elem_samples / HALF_LEN;
+                            for _ in 0..refills {
+                                stream.fill_with(|half| half.fill(0x0000));
+                            }
                         }
-                        
-                        for _ in 0..*duration {
-                            match i2s_tx.write(&pattern) {
-                                Ok(_) => {},
-                                Err(e) => {
-                                    println!("   ❌ Error: {:?}", e);
-                                    break;
+                    }
+                }
+
+                // Long pause between transmissions
+                let pause_samples = SAMPLE_RATE as usize;
+                for _ in 0..(pause_samples / HALF_LEN) {
+                    stream.fill_with(|half| half.fill(0x0000));
+                }
+            }
+
+            led.set_low();
+            println!("   ✅ Morse transmission complete");
+        }
+
+        // FM Pattern 8: PWM Sweep (Variable-Duty Pulse Wave)
+        {
+            led.set_high();
+            println!("🎵 FM Pattern 8/9: PWM Sweep");
+            println!("   🎛️ Pulse wave duty cycle sweeps 5%→50%→5%");
+
+            // Waveform::Pulse exposes the duty cycle the old Square-only
+            // PhaseOsc couldn't: sweeping it is real pulse-width modulation,
+            // not just a frequency trick.
+            let mut pwm_osc = PhaseOsc::new(SAMPLE_RATE);
+            pwm_osc.set_freq(440.0);
+
+            let ramp_samples = (SAMPLE_RATE * 2) as usize;
+            let total_samples = ramp_samples * 2;
+            let refills = total_samples / HALF_LEN;
+            let mut elapsed = 0usize;
+
+            for refill in 0..refills {
+                stream.fill_with(|half| {
+                    for sample in half.iter_mut() {
+                        let t = if elapsed < ramp_samples {
+                            elapsed as f32 / ramp_samples as f32
+                        } else {
+                            1.0 - (elapsed - ramp_samples) as f32 / ramp_samples as f32
+                        };
+                        let duty_percent = (5.0 + 45.0 * t) as u16;
+                        pwm_osc.set_waveform(Waveform::Pulse { duty_percent });
+                        *sample = pwm_osc.next_sample();
+                        elapsed += 1;
+                    }
+                });
+                if refill % (refills / 4).max(1) == 0 {
+                    println!("   🎛️ PWM progress: {}%", (refill * 100) / refills.max(1));
+                }
+            }
+
+            led.set_low();
+            println!("   ✅ PWM sweep complete");
+        }
+
+        // FM Pattern 9: Noise Bursts (LFSR Percussion)
+        {
+            led.set_high();
+            println!("🎵 FM Pattern 9/9: Noise Bursts");
+            println!("   🥁 LFSR noise shaped into snare/hi-hat-like hits");
+
+            // Snare: full 15-bit period, low clock divider (dense, bassy).
+            // Hi-hat: short-period mode (taps bit 6), higher divider (sparser,
+            // more metallic) — same Noise generator, different settings.
+            let hits: [(u16, bool, u32, u32); 2] = [
+                (4, false, 40, 300),  // snare: clock_divider, short_period, attack, release
+                (12, true, 8, 150),   // hi-hat
+            ];
+
+            for (hit_idx, &(clock_divider, short_period, attack_samples, release_samples)) in hits.iter().enumerate() {
+                println!(
+                    "   🥁 Hit {}/{}: {}",
+                    hit_idx + 1,
+                    hits.len(),
+                    if short_period { "hi-hat (short-period)" } else { "snare (full-period)" }
+                );
+
+                for _rep in 0..4 {
+                    let mut noise = Noise::new(0xACE1, clock_divider);
+                    noise.set_short_period(short_period);
+                    let mut burst_envelope = Envelope::new(attack_samples, 1, 1.0, release_samples);
+                    burst_envelope.gate(true);
+
+                    let burst_samples = (attack_samples + release_samples) as usize * 2;
+                    let release_at = burst_samples.saturating_sub(release_samples as usize);
+
+                    let refills = (burst_samples / HALF_LEN).max(1);
+                    let mut elapsed = 0usize;
+                    for _ in 0..refills {
+                        stream.fill_with(|half| {
+                            for sample in half.iter_mut() {
+                                if elapsed == release_at {
+                                    burst_envelope.gate(false);
                                 }
+                                let level = burst_envelope.next_level();
+                                *sample = if noise.next_sample() == 0x8000 {
+                                    (level * 32767.0) as u16
+                                } else {
+                                    0x0000
+                                };
+                                elapsed += 1;
                             }
-                            delay.delay_millis(50);
-                        }
+                        });
+                    }
+
+                    // Short gap between hits
+                    let gap_samples = SAMPLE_RATE as usize / 4;
+                    for _ in 0..(gap_samples / HALF_LEN) {
+                        stream.fill_with(|half| half.fill(0x0000));
                     }
                 }
-                
-                delay.delay_millis(1000); // Long pause between SOS cycles
             }
-            
+
             led.set_low();
-            println!("   ✅ SOS transmission complete");
-            delay.delay_millis(300);
+            println!("   ✅ Noise bursts complete");
         }
-        
+
         println!("✅ Complete FM-style pattern cycle transmitted!");
-        println!("   📻 All 7 FM patterns sent via I2S");
+        println!("   📻 All 9 FM patterns sent via I2S");
         println!("   🎵 Patterns visible as frequency modulation on GPIO12");
-        println!("   📊 Total cycle duration: ~25 seconds");
+        println!("   📡 Streamed gaplessly over circular DMA — no inter-pattern clicks");
         println!("   🔍 Observe different FM characteristics:");
         println!("      📻 Frequency sweeps (smooth changes)");
-        println!("      📡 Amplitude modulation (burst patterns)");  
+        println!("      📡 Amplitude modulation (burst patterns)");
         println!("      🌊 Frequency wobbling (oscillation)");
         println!("      📊 Digital frequency steps");
         println!("      💫 Chirp signals (radar-like)");
         println!("      🎶 Musical note progression");
         println!("      📢 Morse code in FM");
-        
+        println!("      🎛️ PWM duty cycle sweep");
+        println!("      🥁 LFSR noise percussion");
+
         if cycle_count % 2 == 0 {
             println!("🎉 FM Cycle #{} complete - Check oscilloscope for patterns! 📻", cycle_count);
         }
-        
-        println!("⏳ Next FM cycle in 3 seconds...\n");
-        delay.delay_millis(3000);
+
+        println!("⏳ Starting next FM cycle...\n");
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,71 @@
+//! Signal arithmetic: combine two sample sources with a selectable per-sample
+//! operator, so richer oscilloscope shapes can be built from simple
+//! wavetable primitives instead of hand-authored tables.
+
+use crate::oscillator::Oscil;
+
+/// Per-sample combination operator for [`ArithBlock`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ArithOp {
+    /// `a + b`, saturating.
+    Sum,
+    /// `a - b`, saturating.
+    Difference,
+    /// `(a + b) / 2`.
+    Average,
+    /// `a * b`, rescaled back to `i16` range (ring modulation / AM).
+    Product,
+    /// `32767 / a`, guarded against division by (near) zero.
+    Reciprocal,
+    /// `a` raised to an integer power `n`, rescaled back to `i16` range.
+    Power(u32),
+}
+
+/// Combines two sample sources through a selectable arithmetic operator.
+///
+/// Both inputs and the output are full-scale signed 16-bit samples; all math
+/// is fixed point and saturating so a runaway operator (e.g. `Reciprocal`
+/// near zero) can't wrap around and corrupt the output stream.
+pub struct ArithBlock {
+    pub a: Oscil,
+    pub b: Oscil,
+    pub op: ArithOp,
+}
+
+impl ArithBlock {
+    pub fn new(a: Oscil, b: Oscil, op: ArithOp) -> Self {
+        Self { a, b, op }
+    }
+
+    /// Advance both inputs by one sample and return the combined output.
+    pub fn next_sample(&mut self) -> i16 {
+        let a = self.a.next_sample();
+        let b = self.b.next_sample();
+        Self::apply(self.op, a, b)
+    }
+
+    fn apply(op: ArithOp, a: i16, b: i16) -> i16 {
+        match op {
+            ArithOp::Sum => a.saturating_add(b),
+            ArithOp::Difference => a.saturating_sub(b),
+            ArithOp::Average => ((a as i32 + b as i32) / 2) as i16,
+            ArithOp::Product => ((a as i32 * b as i32) >> 15)
+                .clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+            ArithOp::Reciprocal => {
+                if a.unsigned_abs() < 64 {
+                    if a >= 0 { i16::MAX } else { i16::MIN }
+                } else {
+                    (32767i32 / a as i32).clamp(i16::MIN as i32, i16::MAX as i32) as i16
+                }
+            }
+            ArithOp::Power(n) => {
+                // Normalize to [-1, 1) in Q15, exponentiate, rescale back.
+                let mut result = 1i32 << 15;
+                for _ in 0..n {
+                    result = (result * a as i32) >> 15;
+                }
+                result.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! International Morse (CW) keyer.
+//!
+//! The FM pattern's "SOS" demo used a hand-typed `sos_pattern` array of
+//! `(freq, duration)` pairs, so it could only ever play SOS. [`Keyer`] looks
+//! up dot/dash symbols from a full A-Z/0-9 table and turns any `&str` into a
+//! stream of [`Event`]s using standard CW unit timing, so a caller can key a
+//! tone oscillator to transmit arbitrary text.
+
+/// A single Morse symbol.
+#[derive(Clone, Copy)]
+pub enum Symbol {
+    Dot,
+    Dash,
+}
+
+/// Look up the dot/dash pattern for an ASCII letter or digit
+/// (case-insensitive). Anything else (punctuation, etc.) is unsupported.
+pub fn lookup(ch: char) -> Option<&'static [Symbol]> {
+    use Symbol::{Dash, Dot};
+    match ch.to_ascii_uppercase() {
+        'A' => Some(&[Dot, Dash]),
+        'B' => Some(&[Dash, Dot, Dot, Dot]),
+        'C' => Some(&[Dash, Dot, Dash, Dot]),
+        'D' => Some(&[Dash, Dot, Dot]),
+        'E' => Some(&[Dot]),
+        'F' => Some(&[Dot, Dot, Dash, Dot]),
+        'G' => Some(&[Dash, Dash, Dot]),
+        'H' => Some(&[Dot, Dot, Dot, Dot]),
+        'I' => Some(&[Dot, Dot]),
+        'J' => Some(&[Dot, Dash, Dash, Dash]),
+        'K' => Some(&[Dash, Dot, Dash]),
+        'L' => Some(&[Dot, Dash, Dot, Dot]),
+        'M' => Some(&[Dash, Dash]),
+        'N' => Some(&[Dash, Dot]),
+        'O' => Some(&[Dash, Dash, Dash]),
+        'P' => Some(&[Dot, Dash, Dash, Dot]),
+        'Q' => Some(&[Dash, Dash, Dot, Dash]),
+        'R' => Some(&[Dot, Dash, Dot]),
+        'S' => Some(&[Dot, Dot, Dot]),
+        'T' => Some(&[Dash]),
+        'U' => Some(&[Dot, Dot, Dash]),
+        'V' => Some(&[Dot, Dot, Dot, Dash]),
+        'W' => Some(&[Dot, Dash, Dash]),
+        'X' => Some(&[Dash, Dot, Dot, Dash]),
+        'Y' => Some(&[Dash, Dot, Dash, Dash]),
+        'Z' => Some(&[Dash, Dash, Dot, Dot]),
+        '0' => Some(&[Dash, Dash, Dash, Dash, Dash]),
+        '1' => Some(&[Dot, Dash, Dash, Dash, Dash]),
+        '2' => Some(&[Dot, Dot, Dash, Dash, Dash]),
+        '3' => Some(&[Dot, Dot, Dot, Dash, Dash]),
+        '4' => Some(&[Dot, Dot, Dot, Dot, Dash]),
+        '5' => Some(&[Dot, Dot, Dot, Dot, Dot]),
+        '6' => Some(&[Dash, Dot, Dot, Dot, Dot]),
+        '7' => Some(&[Dash, Dash, Dot, Dot, Dot]),
+        '8' => Some(&[Dash, Dash, Dash, Dot, Dot]),
+        '9' => Some(&[Dash, Dash, Dash, Dash, Dot]),
+        _ => None,
+    }
+}
+
+/// One timed keyer event, in standard CW "units" (dot = 1 unit).
+#[derive(Clone, Copy)]
+pub enum Event {
+    /// Key the sidetone on for this many units (a dot or a dash).
+    KeyDown(u32),
+    /// Key the sidetone off for this many units (intra-char/inter-char/word gap).
+    KeyUp(u32),
+}
+
+/// Converts a `&str` message into timed key-down/key-up [`Event`]s:
+/// dot = 1 unit, dash = 3 units, intra-character gap = 1 unit,
+/// inter-character gap = 3 units, word gap = 7 units. Unsupported characters
+/// are silently skipped.
+pub struct Keyer<'a> {
+    chars: core::str::Chars<'a>,
+    symbols: &'static [Symbol],
+    symbol_idx: usize,
+    at_symbol: bool,
+    started: bool,
+}
+
+impl<'a> Keyer<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars(),
+            symbols: &[],
+            symbol_idx: 0,
+            at_symbol: true,
+            started: false,
+        }
+    }
+
+    /// Convert a WPM (words-per-minute) rate into the sample length of one
+    /// CW unit, using the standard `unit_ms = 1200 / wpm` timing formula.
+    pub fn unit_samples(wpm: u32, sample_rate: u32) -> u32 {
+        (sample_rate as u64 * 1200 / wpm as u64 / 1000) as u32
+    }
+
+    /// Pull the next timed event, or `None` once the message is fully keyed.
+    pub fn next_event(&mut self) -> Option<Event> {
+        loop {
+            if self.symbol_idx < self.symbols.len() {
+                if self.at_symbol {
+                    let units = match self.symbols[self.symbol_idx] {
+                        Symbol::Dot => 1,
+                        Symbol::Dash => 3,
+                    };
+                    self.at_symbol = false;
+                    return Some(Event::KeyDown(units));
+                }
+
+                self.symbol_idx += 1;
+                self.at_symbol = true;
+                if self.symbol_idx < self.symbols.len() {
+                    return Some(Event::KeyUp(1)); // intra-character gap
+                }
+                // Falls through to fetch the next character below, which
+                // supplies the (larger) inter-character or word gap.
+                continue;
+            }
+
+            match self.chars.next() {
+                None => return None,
+                Some(' ') => {
+                    self.symbols = &[];
+                    self.symbol_idx = 0;
+                    // No inter-character gap before the next word's first
+                    // letter: the word gap already separates them.
+                    self.started = false;
+                    return Some(Event::KeyUp(7)); // word gap
+                }
+                Some(c) => match lookup(c) {
+                    Some(syms) => {
+                        self.symbols = syms;
+                        self.symbol_idx = 0;
+                        self.at_symbol = true;
+                        if self.started {
+                            return Some(Event::KeyUp(3)); // inter-character gap
+                        }
+                        self.started = true;
+                        // First character: no leading gap, loop back to key it.
+                    }
+                    None => continue, // unsupported character, skip it
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! 15-bit LFSR pseudo-random noise source, in the style of the SID/GBA sound
+//! cores' noise channels — every other generator in this crate is periodic,
+//! so there was no source for percussion, radar-clutter, or wind effects.
+
+/// Linear-feedback-shift-register noise generator.
+pub struct Noise {
+    state: u16,
+    clock_divider: u16,
+    divider_counter: u16,
+    short_period: bool,
+    output: u16,
+}
+
+impl Noise {
+    /// `seed` must be nonzero (an all-zero state never changes).
+    /// `clock_divider` sets the noise "pitch": the LFSR only advances every
+    /// `clock_divider` output samples.
+    pub fn new(seed: u16, clock_divider: u16) -> Self {
+        Self {
+            state: if seed == 0 { 1 } else { seed },
+            clock_divider: clock_divider.max(1),
+            divider_counter: 0,
+            short_period: false,
+            output: 0x0000,
+        }
+    }
+
+    pub fn set_clock_divider(&mut self, clock_divider: u16) {
+        self.clock_divider = clock_divider.max(1);
+    }
+
+    /// Tap bit 6 instead of bit 14: a much shorter repeat period that sounds
+    /// more tonal/metallic than the full 15-bit sequence.
+    pub fn set_short_period(&mut self, short_period: bool) {
+        self.short_period = short_period;
+    }
+
+    /// Advance by one sample and return `0x8000`/`0x0000`, matching the
+    /// other FM-pattern oscillators' HIGH/LOW convention.
+    pub fn next_sample(&mut self) -> u16 {
+        self.divider_counter += 1;
+        if self.divider_counter >= self.clock_divider {
+            self.divider_counter = 0;
+
+            let new_bit = (self.state ^ (self.state >> 1)) & 1;
+            self.state >>= 1;
+            let tap_bit = if self.short_period { 6 } else { 14 };
+            self.state |= new_bit << tap_bit;
+
+            self.output = if self.state & 1 == 1 { 0x8000 } else { 0x0000 };
+        }
+        self.output
+    }
+}
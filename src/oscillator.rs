@@ -0,0 +1,167 @@
+//! Phase-accumulator wavetable oscillator.
+//!
+//! Replaces the static `VISUAL_PATTERNS` tables with a runtime synth that can
+//! be tuned to any pitch: a single-cycle table is walked by a 32-bit phase
+//! accumulator so the output frequency is decoupled from the table length.
+
+/// Number of entries in each single-cycle wavetable.
+pub const TABLE_LEN: usize = 256;
+
+/// Fractional bits kept below the table index in the phase accumulator.
+const FRAC_BITS: u32 = 16;
+
+/// Single-cycle sine table, `i16` centered on zero (one period over `TABLE_LEN` samples).
+pub static SINE_TABLE: [i16; TABLE_LEN] = [
+    0, 804, 1608, 2410, 3212, 4011, 4808, 5602,
+    6393, 7179, 7962, 8739, 9512, 10278, 11039, 11793,
+    12539, 13279, 14010, 14732, 15446, 16151, 16846, 17530,
+    18204, 18868, 19519, 20159, 20787, 21403, 22005, 22594,
+    23170, 23731, 24279, 24811, 25329, 25832, 26319, 26790,
+    27245, 27683, 28105, 28510, 28898, 29268, 29621, 29956,
+    30273, 30571, 30852, 31113, 31356, 31580, 31785, 31971,
+    32137, 32285, 32412, 32521, 32609, 32678, 32728, 32757,
+    32767, 32757, 32728, 32678, 32609, 32521, 32412, 32285,
+    32137, 31971, 31785, 31580, 31356, 31113, 30852, 30571,
+    30273, 29956, 29621, 29268, 28898, 28510, 28105, 27683,
+    27245, 26790, 26319, 25832, 25329, 24811, 24279, 23731,
+    23170, 22594, 22005, 21403, 20787, 20159, 19519, 18868,
+    18204, 17530, 16846, 16151, 15446, 14732, 14010, 13279,
+    12539, 11793, 11039, 10278, 9512, 8739, 7962, 7179,
+    6393, 5602, 4808, 4011, 3212, 2410, 1608, 804,
+    0, -804, -1608, -2410, -3212, -4011, -4808, -5602,
+    -6393, -7179, -7962, -8739, -9512, -10278, -11039, -11793,
+    -12539, -13279, -14010, -14732, -15446, -16151, -16846, -17530,
+    -18204, -18868, -19519, -20159, -20787, -21403, -22005, -22594,
+    -23170, -23731, -24279, -24811, -25329, -25832, -26319, -26790,
+    -27245, -27683, -28105, -28510, -28898, -29268, -29621, -29956,
+    -30273, -30571, -30852, -31113, -31356, -31580, -31785, -31971,
+    -32137, -32285, -32412, -32521, -32609, -32678, -32728, -32757,
+    -32767, -32757, -32728, -32678, -32609, -32521, -32412, -32285,
+    -32137, -31971, -31785, -31580, -31356, -31113, -30852, -30571,
+    -30273, -29956, -29621, -29268, -28898, -28510, -28105, -27683,
+    -27245, -26790, -26319, -25832, -25329, -24811, -24279, -23731,
+    -23170, -22594, -22005, -21403, -20787, -20159, -19519, -18868,
+    -18204, -17530, -16846, -16151, -15446, -14732, -14010, -13279,
+    -12539, -11793, -11039, -10278, -9512, -8739, -7962, -7179,
+    -6393, -5602, -4808, -4011, -3212, -2410, -1608, -804,
+];
+
+/// Single-cycle sawtooth table: ramps linearly from -32768 up to 32512 in steps of 256.
+pub static SAW_TABLE: [i16; TABLE_LEN] = build_saw_table();
+
+/// Single-cycle square table: first half high, second half low.
+pub static SQUARE_TABLE: [i16; TABLE_LEN] = build_square_table();
+
+/// Single-cycle triangle table: ramps up then down.
+pub static TRIANGLE_TABLE: [i16; TABLE_LEN] = build_triangle_table();
+
+/// Single-cycle pseudo-random noise table (fixed seed, not regenerated per boot).
+pub static NOISE_TABLE: [i16; TABLE_LEN] = build_noise_table();
+
+const fn build_saw_table() -> [i16; TABLE_LEN] {
+    let mut table = [0i16; TABLE_LEN];
+    let mut i = 0;
+    while i < TABLE_LEN {
+        table[i] = (i as i32 * 65536 / TABLE_LEN as i32 - 32768) as i16;
+        i += 1;
+    }
+    table
+}
+
+const fn build_square_table() -> [i16; TABLE_LEN] {
+    let mut table = [0i16; TABLE_LEN];
+    let mut i = 0;
+    while i < TABLE_LEN {
+        table[i] = if i < TABLE_LEN / 2 { 32767 } else { -32768 };
+        i += 1;
+    }
+    table
+}
+
+const fn build_triangle_table() -> [i16; TABLE_LEN] {
+    let mut table = [0i16; TABLE_LEN];
+    let mut i = 0;
+    while i < TABLE_LEN {
+        let quarter = TABLE_LEN / 4;
+        let v = if i < quarter {
+            (i as i32 * 32767 / quarter as i32) as i16
+        } else if i < 3 * quarter {
+            (32767 - (i - quarter) as i32 * 65534 / (2 * quarter) as i32) as i16
+        } else {
+            (-32767 + (i - 3 * quarter) as i32 * 32767 / quarter as i32) as i16
+        };
+        table[i] = v;
+        i += 1;
+    }
+    table
+}
+
+/// A fixed 15-bit LFSR seed, unrolled at compile time into a single-cycle table.
+const fn build_noise_table() -> [i16; TABLE_LEN] {
+    let mut table = [0i16; TABLE_LEN];
+    let mut state: u16 = 0xACE1;
+    let mut i = 0;
+    while i < TABLE_LEN {
+        let bit = (state ^ (state >> 1)) & 1;
+        state = (state >> 1) | (bit << 14);
+        table[i] = if state & 1 != 0 { 32767 } else { -32768 };
+        i += 1;
+    }
+    table
+}
+
+/// Phase-accumulator oscillator over a single-cycle wavetable.
+///
+/// `phase` is a `u32` whose bits above the low [`FRAC_BITS`] (masked to
+/// `TABLE_LEN`) give the table index, with the low [`FRAC_BITS`] used as a
+/// fractional offset for linear interpolation.
+pub struct Oscil {
+    table: &'static [i16; TABLE_LEN],
+    phase: u32,
+    phase_inc: u32,
+    sample_rate: u32,
+}
+
+impl Oscil {
+    pub fn new(table: &'static [i16; TABLE_LEN], sample_rate: u32) -> Self {
+        let mut osc = Self {
+            table,
+            phase: 0,
+            phase_inc: 0,
+            sample_rate,
+        };
+        osc.set_freq(0.0);
+        osc
+    }
+
+    /// Re-target the oscillator at a different wavetable without resetting phase.
+    pub fn set_table(&mut self, table: &'static [i16; TABLE_LEN]) {
+        self.table = table;
+    }
+
+    /// Set the oscillator frequency in Hz.
+    pub fn set_freq(&mut self, freq_hz: f32) {
+        let inc = (freq_hz as f64) * (TABLE_LEN as f64) * (1u64 << FRAC_BITS) as f64
+            / self.sample_rate as f64;
+        self.phase_inc = inc as u32;
+    }
+
+    /// Advance the oscillator by one sample and return the interpolated value.
+    pub fn next_sample(&mut self) -> i16 {
+        let index = (self.phase >> FRAC_BITS) as usize & (TABLE_LEN - 1);
+        let next_index = (index + 1) & (TABLE_LEN - 1);
+        let frac = (self.phase & ((1 << FRAC_BITS) - 1)) as i64;
+
+        let a = self.table[index] as i64;
+        let b = self.table[next_index] as i64;
+        let sample = a + (((b - a) * frac) >> FRAC_BITS);
+
+        self.phase = self.phase.wrapping_add(self.phase_inc);
+        sample as i16
+    }
+
+    /// Scale a signed sample into the I2S `u16` TX range (unsigned, offset-biased).
+    pub fn to_i2s(sample: i16) -> u16 {
+        (sample as i32 + 32768) as u16
+    }
+}
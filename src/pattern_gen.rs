@@ -11,7 +11,7 @@ use esp_hal::{
     dma::{Dma, DmaPriority},
     dma_buffers,
     gpio::{Io, Level, Output},
-    i2s::{DataFormat, I2s, I2sWrite, Standard},
+    i2s::{DataFormat, I2s, I2sRead, Standard},
     peripherals::Peripherals,
     prelude::*,
     system::SystemControl,
@@ -20,52 +20,66 @@ use esp_println::println;
 use esp_backtrace as _;
 use esp_hal::entry;
 
-// Visual pattern data for oscilloscope viewing
-// Each pattern creates distinct shapes when viewed on oscilloscope
-const VISUAL_PATTERNS: &[&[u16]] = &[
-    &SQUARE_WAVE_PATTERN,
-    &TRIANGLE_WAVE_PATTERN,
-    &SAWTOOTH_PATTERN,
-    &STAIRCASE_PATTERN,
-    &HEART_SHAPE_PATTERN,
-    &HOUSE_PATTERN,
-    &SMILEY_FACE_PATTERN,
-];
+mod filter_bank;
+mod mixer;
+mod oscillator;
+mod stream;
+mod synth_voice;
+mod xy;
+use filter_bank::FilterBank;
+use mixer::{ArithBlock, ArithOp};
+use oscillator::{Oscil, NOISE_TABLE, SAW_TABLE, SINE_TABLE, SQUARE_TABLE, TRIANGLE_TABLE};
+use stream::StreamWriter;
+use synth_voice::{Adsr, Voice};
+use xy::XyPattern;
 
-// Pattern 1: Square Wave - Clean rectangular pulses
-const SQUARE_WAVE_PATTERN: &[u16] = &[
-    0x8000, 0x8000, 0x8000, 0x8000, 0x8000, 0x8000, 0x8000, 0x8000, // High
-    0x8000, 0x8000, 0x8000, 0x8000, 0x8000, 0x8000, 0x8000, 0x8000,
-    0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, // Low
-    0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000,
-    0x8000, 0x8000, 0x8000, 0x8000, 0x8000, 0x8000, 0x8000, 0x8000, // High
-    0x8000, 0x8000, 0x8000, 0x8000, 0x8000, 0x8000, 0x8000, 0x8000,
-    0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, // Low
-    0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000,
-];
+/// Number of (x, y) pairs per XY vector-mode pattern; each pair fills two
+/// slots (L=X, R=Y) of the interleaved TX buffer.
+const XY_POINTS: usize = TX_BUFFER_SIZE / 2;
 
-// Pattern 2: Triangle Wave - Smooth ramps up and down
-const TRIANGLE_WAVE_PATTERN: &[u16] = &[
-    0x0000, 0x1000, 0x2000, 0x3000, 0x4000, 0x5000, 0x6000, 0x7000, // Rising
-    0x8000, 0x7000, 0x6000, 0x5000, 0x4000, 0x3000, 0x2000, 0x1000, // Falling
-    0x0000, 0x1000, 0x2000, 0x3000, 0x4000, 0x5000, 0x6000, 0x7000, // Rising
-    0x8000, 0x7000, 0x6000, 0x5000, 0x4000, 0x3000, 0x2000, 0x1000, // Falling
-    0x0000, 0x1000, 0x2000, 0x3000, 0x4000, 0x5000, 0x6000, 0x7000, // Rising
-    0x8000, 0x7000, 0x6000, 0x5000, 0x4000, 0x3000, 0x2000, 0x1000, // Falling
-    0x0000, 0x1000, 0x2000, 0x3000, 0x4000, 0x5000, 0x6000, 0x7000, // Rising
-    0x8000, 0x7000, 0x6000, 0x5000, 0x4000, 0x3000, 0x2000, 0x1000, // Falling
-];
+/// Samples per half of the circular streaming buffer (see [`stream`]).
+/// `TX_BUFFER_SIZE` is a *byte* count (as `dma_buffers!` expects), and each
+/// half holds `u16` samples, so this is `TX_BUFFER_SIZE / 2 / 2`.
+const HALF_LEN: usize = TX_BUFFER_SIZE / 4;
+/// How many halves to stream per pattern before moving to the next one.
+const HALVES_PER_PATTERN: usize = 40;
+
+/// A pattern is either synthesized at runtime from a wavetable + pitch, a
+/// SID-style plucked voice (oscillator -> ADSR -> resonant low-pass), or a
+/// hand-authored shape played back verbatim (the XY-plottable art patterns).
+enum PatternSource {
+    Tone(&'static [i16; oscillator::TABLE_LEN], f32),
+    Pluck(&'static [i16; oscillator::TABLE_LEN], f32),
+    /// Two tables run through an [`ArithBlock`]: (table_a, freq_a, table_b, freq_b, op).
+    Mix(
+        &'static [i16; oscillator::TABLE_LEN],
+        f32,
+        &'static [i16; oscillator::TABLE_LEN],
+        f32,
+        ArithOp,
+    ),
+    /// A true XY vector-mode pattern: `(freq_ratio_a, freq_ratio_b, delta_rad)`.
+    Xy(f32, f32, f32),
+    Shape(&'static [u16]),
+}
 
-// Pattern 3: Sawtooth Wave - Sharp rise, quick fall
-const SAWTOOTH_PATTERN: &[u16] = &[
-    0x0000, 0x0800, 0x1000, 0x1800, 0x2000, 0x2800, 0x3000, 0x3800,
-    0x4000, 0x4800, 0x5000, 0x5800, 0x6000, 0x6800, 0x7000, 0x7800,
-    0x8000, 0x0000, 0x0800, 0x1000, 0x1800, 0x2000, 0x2800, 0x3000,
-    0x3800, 0x4000, 0x4800, 0x5000, 0x5800, 0x6000, 0x6800, 0x7000,
-    0x7800, 0x8000, 0x0000, 0x0800, 0x1000, 0x1800, 0x2000, 0x2800,
-    0x3000, 0x3800, 0x4000, 0x4800, 0x5000, 0x5800, 0x6000, 0x6800,
-    0x7000, 0x7800, 0x8000, 0x0000, 0x0800, 0x1000, 0x1800, 0x2000,
-    0x2800, 0x3000, 0x3800, 0x4000, 0x4800, 0x5000, 0x5800, 0x6000,
+// Visual pattern sequence for oscilloscope viewing.
+// The first five are synthesized live through the wavetable oscillator so
+// their pitch is tunable; the art shapes are still played back from their
+// hand-authored tables since an oscillator can't express a heart outline.
+const VISUAL_PATTERNS: &[PatternSource] = &[
+    PatternSource::Tone(&SQUARE_TABLE, 220.0),
+    PatternSource::Tone(&TRIANGLE_TABLE, 220.0),
+    PatternSource::Tone(&SAW_TABLE, 220.0),
+    PatternSource::Tone(&SINE_TABLE, 440.0),
+    PatternSource::Tone(&NOISE_TABLE, 880.0),
+    PatternSource::Pluck(&SAW_TABLE, 220.0),
+    PatternSource::Mix(&SINE_TABLE, 220.0, &SINE_TABLE, 280.0, ArithOp::Product),
+    PatternSource::Xy(3.0, 2.0, core::f32::consts::FRAC_PI_2),
+    PatternSource::Shape(&STAIRCASE_PATTERN),
+    PatternSource::Shape(&HEART_SHAPE_PATTERN),
+    PatternSource::Shape(&HOUSE_PATTERN),
+    PatternSource::Shape(&SMILEY_FACE_PATTERN),
 ];
 
 // Pattern 4: Staircase - Digital steps creating a ladder effect
@@ -165,7 +179,7 @@ fn main() -> ! {
     println!("✅ DMA configured");
 
     println!("📊 Creating DMA buffers ({} bytes each)...", TX_BUFFER_SIZE);
-    let (_rx_buffer, rx_descriptors, tx_buffer, tx_descriptors) = 
+    let (rx_buffer, rx_descriptors, tx_buffer, tx_descriptors) =
         dma_buffers!(RX_BUFFER_SIZE, TX_BUFFER_SIZE);
     println!("✅ DMA buffers created");
 
@@ -190,16 +204,27 @@ fn main() -> ! {
     let dout = io.pins.gpio6;
     
     println!("🔧 Building I2S TX interface...");
-    let mut i2s_tx = i2s.i2s_tx
+    let i2s_tx = i2s.i2s_tx
         .with_bclk(bclk)
         .with_ws(ws)
         .with_dout(dout)
         .build();
-    
+
+    // Keep the I2S clock running continuously: a persistent circular DMA
+    // transfer over `tx_buffer`, refilled half-by-half as the hardware
+    // drains it, instead of blocking write()+delay() calls with dead gaps.
+    let mut stream = StreamWriter::<_, HALF_LEN>::start(i2s_tx, tx_buffer);
+
+    // Configure I2S RX pin (shares BCLK/WS with TX, full-duplex) for the
+    // audio-reactive input path that drives the filter bank below.
+    let din = io.pins.gpio7;
+    let mut i2s_rx = i2s.i2s_rx.with_din(din).build();
+
     println!("✅ I2S TX Configuration Complete:");
     println!("   🔌 BCLK: GPIO4 (Bit Clock)");
-    println!("   🔌 WS:   GPIO5 (Word Select/Frame Sync)"); 
+    println!("   🔌 WS:   GPIO5 (Word Select/Frame Sync)");
     println!("   🔌 DOUT: GPIO6 (Data Out)");
+    println!("   🔌 DIN:  GPIO7 (Audio-reactive input)");
     println!("   📊 Sample Rate: {} Hz", SAMPLE_RATE);
     println!("   🎼 Format: 16-bit, Philips I2S");
     println!();
@@ -209,6 +234,8 @@ fn main() -> ! {
     println!("   📏 Time scale: ~1ms/div for best viewing");
     println!("   📈 Voltage scale: ~1V/div");
     println!("   🎯 Look for geometric patterns!");
+    println!("   🩻 For the XY Lissajous pattern, switch the scope to XY mode");
+    println!("      (CH1=X, CH2=Y on the L/R channels of this Data16Channel16 stream)");
     println!();
     println!("🚀 Pattern sequence:");
     println!("   1️⃣  Square Waves - Clean rectangles");
@@ -222,53 +249,114 @@ fn main() -> ! {
 
     let mut pattern_index = 0;
     let mut transmission_count = 0;
+    let mut osc = Oscil::new(&SQUARE_TABLE, SAMPLE_RATE);
+    let mut tone_buffer = [0u16; TX_BUFFER_SIZE];
+    let mut filter_bank = FilterBank::new(80.0, 6000.0, 20.0, SAMPLE_RATE);
+    let mut rx_samples = [0i16; RX_BUFFER_SIZE / 2];
 
     loop {
         transmission_count += 1;
         led.set_high();
-        
-        let current_pattern = VISUAL_PATTERNS[pattern_index];
+
+        // Capture a block from the line/mic input and find the dominant band.
+        match i2s_rx.read(rx_buffer) {
+            Ok(_) => {
+                for (sample, bytes) in rx_samples.iter_mut().zip(rx_buffer.chunks_exact(2)) {
+                    *sample = i16::from_le_bytes([bytes[0], bytes[1]]);
+                }
+                let active_band = filter_bank.process(&rx_samples);
+                println!("   🎚️  Input-reactive: dominant band {}/{}", active_band + 1, filter_bank::NUM_BANDS);
+                for _ in 0..=active_band {
+                    led.set_high();
+                    delay.delay_millis(15);
+                    led.set_low();
+                    delay.delay_millis(15);
+                }
+            }
+            Err(e) => println!("   ⚠️  RX capture error: {:?}", e),
+        }
+
         let pattern_names = [
-            "Square Wave", "Triangle Wave", "Sawtooth", "Staircase", 
-            "Heart Shape", "House Pattern", "Smiley Face"
+            "Square Wave", "Triangle Wave", "Sawtooth", "Sine Tone", "Noise Burst",
+            "Synth Pluck", "Ring Mod Mix", "XY Lissajous", "Staircase", "Heart Shape", "House Pattern", "Smiley Face"
         ];
-        
-        println!("🎨 === PATTERN {}: {} === (Transmission #{}) 🎨", 
+
+        println!("🎨 === PATTERN {}: {} === (Transmission #{}) 🎨",
                  pattern_index + 1, pattern_names[pattern_index], transmission_count);
-        
-        // Send pattern multiple times for good oscilloscope capture
-        for repeat in 0..10 {
-            println!("   📡 Repeat {}/10 - Transmitting {} samples", repeat + 1, current_pattern.len());
-            
-            // Send the pattern
-            match i2s_tx.write(current_pattern) {
-                Ok(_) => {
-                    if repeat % 3 == 0 {
-                        println!("   ✅ Pattern sent successfully");
-                    }
+
+        let current_pattern: &[u16] = match &VISUAL_PATTERNS[pattern_index] {
+            PatternSource::Tone(table, freq_hz) => {
+                osc.set_table(table);
+                osc.set_freq(*freq_hz);
+                for sample in tone_buffer.iter_mut() {
+                    *sample = Oscil::to_i2s(osc.next_sample());
+                }
+                &tone_buffer
+            }
+            PatternSource::Pluck(table, freq_hz) => {
+                let mut voice = Voice::new(
+                    Oscil::new(table, SAMPLE_RATE),
+                    Adsr::new(10, 150, 0x4000, 400, SAMPLE_RATE),
+                    3000.0,
+                    2500.0,
+                    4.0,
+                    SAMPLE_RATE,
+                );
+                voice.osc.set_freq(*freq_hz);
+                voice.gate(true);
+                for sample in tone_buffer.iter_mut() {
+                    *sample = Oscil::to_i2s(voice.next_sample());
                 }
-                Err(e) => {
-                    println!("   ❌ Error sending pattern: {:?}", e);
-                    break;
+                &tone_buffer
+            }
+            PatternSource::Mix(table_a, freq_a, table_b, freq_b, op) => {
+                let mut osc_a = Oscil::new(table_a, SAMPLE_RATE);
+                let mut osc_b = Oscil::new(table_b, SAMPLE_RATE);
+                osc_a.set_freq(*freq_a);
+                osc_b.set_freq(*freq_b);
+                let mut block = ArithBlock::new(osc_a, osc_b, *op);
+                for sample in tone_buffer.iter_mut() {
+                    *sample = Oscil::to_i2s(block.next_sample());
+                }
+                &tone_buffer
+            }
+            PatternSource::Xy(freq_ratio_a, freq_ratio_b, delta_rad) => {
+                let curve = XyPattern::<XY_POINTS>::lissajous(*freq_ratio_a, *freq_ratio_b, *delta_rad);
+                curve.interleave_into(&mut tone_buffer);
+                &tone_buffer
+            }
+            PatternSource::Shape(samples) => samples,
+        };
+
+        // Stream the pattern continuously through the circular DMA transfer
+        // (no blocking write()/delay() gaps between halves).
+        println!("   📡 Streaming {} samples continuously ({} half-buffer refills)", current_pattern.len(), HALVES_PER_PATTERN);
+        let mut cursor = 0usize;
+        for refill in 0..HALVES_PER_PATTERN {
+            stream.fill_with(|half| {
+                for sample in half.iter_mut() {
+                    *sample = current_pattern[cursor];
+                    cursor = (cursor + 1) % current_pattern.len();
                 }
+            });
+            if refill % 10 == 0 {
+                println!("   ✅ Half-buffer {}/{} streamed", refill + 1, HALVES_PER_PATTERN);
             }
-            
-            delay.delay_millis(10); // Small delay between repeats
         }
-        
+
         led.set_low();
-        
+
         println!("✅ Pattern {} complete!", pattern_names[pattern_index]);
         println!("   📊 Pattern size: {} samples", current_pattern.len());
         println!("   🎯 Check oscilloscope for visual pattern!");
-        
+
         // Move to next pattern
         pattern_index = (pattern_index + 1) % VISUAL_PATTERNS.len();
-        
+
         if pattern_index == 0 {
             println!("\n🎉 Completed full pattern cycle! Starting over...\n");
         }
-        
+
         println!("⏳ Waiting 2 seconds before next pattern...\n");
         delay.delay_millis(2000);
     }
@@ -0,0 +1,142 @@
+//! Event-driven note sequencer.
+//!
+//! The FM patterns used nested loops over magic arrays like
+//! `notes = [16, 14, 12, ...]` or `frequencies = [2, 4, 6, 8, ...]`, so
+//! writing a new tune meant writing new code. [`Sequencer`] instead walks a
+//! `const` slice of [`NoteEvent`]s (in the spirit of SuperCollider's
+//! `Pbind`/ChucK note arrays), retriggering the envelope gate at each note
+//! boundary and feeding [`PhaseOsc`] — so a song is a data table.
+
+use crate::dds::{PhaseOsc, Waveform};
+use crate::envelope::Envelope;
+
+/// One note: pitch, duration, target amplitude, and waveform shape.
+#[derive(Clone, Copy)]
+pub struct NoteEvent {
+    pub freq_hz: f32,
+    pub dur_ms: u16,
+    pub amp: f32,
+    pub waveform: Waveform,
+}
+
+/// Drives a [`PhaseOsc`] through a slice of [`NoteEvent`]s. Every note gets
+/// the same attack/release shape (in samples); its own `amp` is the
+/// envelope's sustain level.
+pub struct Sequencer<'a> {
+    events: &'a [NoteEvent],
+    index: usize,
+    osc: PhaseOsc,
+    envelope: Envelope,
+    sample_rate: u32,
+    attack_samples: u32,
+    release_samples: u32,
+    samples_left: u32,
+    release_at: u32,
+    finished: bool,
+}
+
+impl<'a> Sequencer<'a> {
+    pub fn new(events: &'a [NoteEvent], sample_rate: u32, attack_samples: u32, release_samples: u32) -> Self {
+        let mut seq = Self {
+            events,
+            index: 0,
+            osc: PhaseOsc::new(sample_rate),
+            envelope: Envelope::new(attack_samples, 1, 1.0, release_samples),
+            sample_rate,
+            attack_samples,
+            release_samples,
+            samples_left: 0,
+            release_at: 0,
+            finished: events.is_empty(),
+        };
+        if !seq.finished {
+            seq.trigger(0);
+        }
+        seq
+    }
+
+    fn trigger(&mut self, index: usize) {
+        let event = self.events[index];
+        self.osc.set_freq(event.freq_hz);
+        self.osc.set_waveform(event.waveform);
+        self.envelope = Envelope::new(self.attack_samples, 1, event.amp, self.release_samples);
+        self.envelope.gate(true);
+
+        let dur_samples = (event.dur_ms as u32 * self.sample_rate / 1000).max(1);
+        self.samples_left = dur_samples;
+        self.release_at = dur_samples.saturating_sub(self.release_samples);
+    }
+
+    /// Restart from the first event (e.g. to loop a sequence).
+    pub fn restart(&mut self) {
+        self.index = 0;
+        self.finished = self.events.is_empty();
+        if !self.finished {
+            self.trigger(0);
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Advance by one sample, returning the I2S output level, or `None` once
+    /// every event has played.
+    ///
+    /// Amplitude shaping multiplies the envelope level directly into the
+    /// (de-biased) oscillator sample, the way `synth_voice::Adsr::apply`
+    /// does — threshold-gating against `HIGH` only works for a true
+    /// two-level `Waveform::Square`, and silences anything else (e.g. the
+    /// `Sine`-voiced `MAJOR_SCALE`).
+    pub fn next_sample(&mut self) -> Option<u16> {
+        if self.finished {
+            return None;
+        }
+
+        if self.samples_left == self.release_at {
+            self.envelope.gate(false);
+        }
+
+        let level = self.envelope.next_level();
+        let raw = self.osc.next_sample() as i32 - 0x8000;
+        let sample = (raw as f32 * level) as i32 + 0x8000;
+
+        self.samples_left -= 1;
+        if self.samples_left == 0 {
+            self.index += 1;
+            if self.index < self.events.len() {
+                self.trigger(self.index);
+            } else {
+                self.finished = true;
+            }
+        }
+
+        Some(sample.clamp(0x0000, 0xFFFF) as u16)
+    }
+}
+
+/// C major scale (C4 through C5), as real musical frequencies. Played as a
+/// sine tone rather than a square wave, since it's meant to sound like notes.
+pub const MAJOR_SCALE: &[NoteEvent] = &[
+    NoteEvent { freq_hz: 261.63, dur_ms: 480, amp: 1.0, waveform: Waveform::Sine },
+    NoteEvent { freq_hz: 293.66, dur_ms: 480, amp: 1.0, waveform: Waveform::Sine },
+    NoteEvent { freq_hz: 329.63, dur_ms: 480, amp: 1.0, waveform: Waveform::Sine },
+    NoteEvent { freq_hz: 349.23, dur_ms: 480, amp: 1.0, waveform: Waveform::Sine },
+    NoteEvent { freq_hz: 392.00, dur_ms: 480, amp: 1.0, waveform: Waveform::Sine },
+    NoteEvent { freq_hz: 440.00, dur_ms: 480, amp: 1.0, waveform: Waveform::Sine },
+    NoteEvent { freq_hz: 493.88, dur_ms: 480, amp: 1.0, waveform: Waveform::Sine },
+    NoteEvent { freq_hz: 523.25, dur_ms: 480, amp: 1.0, waveform: Waveform::Sine },
+];
+
+/// Discrete step-frequency tune (500Hz up to 4kHz, then back down to 1kHz), the data-driven
+/// equivalent of the old `frequencies = [2, 4, 6, 8, 12, 16, 8, 4]` array.
+pub const STEP_TUNE: &[NoteEvent] = &[
+    NoteEvent { freq_hz: 500.0, dur_ms: 420, amp: 1.0, waveform: Waveform::Square },
+    NoteEvent { freq_hz: 1000.0, dur_ms: 420, amp: 1.0, waveform: Waveform::Square },
+    NoteEvent { freq_hz: 1500.0, dur_ms: 420, amp: 1.0, waveform: Waveform::Square },
+    NoteEvent { freq_hz: 2000.0, dur_ms: 420, amp: 1.0, waveform: Waveform::Square },
+    NoteEvent { freq_hz: 3000.0, dur_ms: 420, amp: 1.0, waveform: Waveform::Square },
+    NoteEvent { freq_hz: 4000.0, dur_ms: 420, amp: 1.0, waveform: Waveform::Square },
+    NoteEvent { freq_hz: 2000.0, dur_ms: 420, amp: 1.0, waveform: Waveform::Square },
+    NoteEvent { freq_hz: 1000.0, dur_ms: 420, amp: 1.0, waveform: Waveform::Square },
+];
@@ -0,0 +1,53 @@
+//! Glitch-free continuous output: a persistent, circularly-running DMA
+//! transfer with two halves so the I2S clock never stops between patterns.
+//!
+//! Instead of `i2s_tx.write(pattern)` + `delay_millis(...)` (which leaves
+//! silent/frozen gaps between blocking writes), [`StreamWriter`] keeps one
+//! `I2sWriteDma` transfer running for the life of the program and hands the
+//! caller whichever half the DMA engine has already drained, via
+//! [`StreamWriter::fill_with`].
+
+use esp_hal::i2s::I2sWriteDma;
+
+/// Owns a circular double-buffered I2S TX transfer. `HALF_LEN` is the number
+/// of `u16` samples in one half of the buffer (`TX_BUFFER_SIZE / 4`, since
+/// `TX_BUFFER_SIZE` is a byte count covering both halves of `u16` samples).
+pub struct StreamWriter<TX, const HALF_LEN: usize> {
+    transfer: TX,
+}
+
+impl<TX, const HALF_LEN: usize> StreamWriter<TX, HALF_LEN>
+where
+    TX: I2sWriteDma<'static>,
+{
+    /// Start a persistent circular transfer over `buffer` (its length must
+    /// be `2 * HALF_LEN` samples, i.e. `4 * HALF_LEN` bytes).
+    pub fn start(i2s_tx: TX, buffer: &'static mut [u8]) -> Self {
+        let transfer = i2s_tx
+            .write_dma_circular(buffer)
+            .expect("failed to start circular I2S TX transfer");
+        Self { transfer }
+    }
+
+    /// Block until at least one half's worth of room has drained out, fill
+    /// that much with fresh samples from `render` (raw `u16` I2S samples,
+    /// sent little-endian), and push it back into the circular transfer.
+    pub fn fill_with<F>(&mut self, mut render: F)
+    where
+        F: FnMut(&mut [u16; HALF_LEN]),
+    {
+        while self.transfer.available().unwrap_or(0) < HALF_LEN * 2 {
+            // Wait for the DMA engine to drain at least one half.
+        }
+
+        let mut samples = [0u16; HALF_LEN];
+        render(&mut samples);
+
+        let mut bytes = [0u8; HALF_LEN * 2];
+        for (chunk, sample) in bytes.chunks_exact_mut(2).zip(samples.iter()) {
+            chunk.copy_from_slice(&sample.to_le_bytes());
+        }
+
+        let _ = self.transfer.push(&bytes);
+    }
+}
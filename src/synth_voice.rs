@@ -0,0 +1,215 @@
+//! SID-style subtractive synthesis voice: an [`Oscil`] shaped by an ADSR
+//! envelope and swept through a resonant low-pass biquad, in the spirit of
+//! the classic MOS6581 voice architecture.
+
+use crate::oscillator::Oscil;
+
+/// ADSR stage.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Idle,
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+/// Fixed-point ADSR envelope generator.
+///
+/// `level` is a `u16` in `0..=0x7FFF` (Q15) that callers multiply into the
+/// oscillator sample. Attack/decay/release are expressed as rates per
+/// sample, derived from their times in milliseconds at construction time.
+pub struct Adsr {
+    stage: Stage,
+    level: u16,
+    attack_rate: u16,
+    decay_rate: u16,
+    sustain_level: u16,
+    release_rate: u16,
+}
+
+impl Adsr {
+    const FULL: u32 = 0x7FFF;
+
+    pub fn new(attack_ms: u32, decay_ms: u32, sustain_level: u16, release_ms: u32, sample_rate: u32) -> Self {
+        Self {
+            stage: Stage::Idle,
+            level: 0,
+            attack_rate: Self::rate_for(attack_ms, sample_rate, Self::FULL),
+            decay_rate: Self::rate_for(decay_ms, sample_rate, Self::FULL - sustain_level as u32),
+            sustain_level,
+            release_rate: Self::rate_for(release_ms, sample_rate, Self::FULL),
+        }
+    }
+
+    fn rate_for(time_ms: u32, sample_rate: u32, span: u32) -> u16 {
+        let samples = (time_ms * sample_rate / 1000).max(1);
+        ((span / samples).max(1)) as u16
+    }
+
+    /// Gate the envelope on (retrigger Attack) or off (jump to Release).
+    pub fn gate(&mut self, on: bool) {
+        self.stage = if on { Stage::Attack } else { Stage::Release };
+    }
+
+    /// Advance the envelope by one sample and return the current level (0..=0x7FFF).
+    pub fn next_level(&mut self) -> u16 {
+        match self.stage {
+            Stage::Idle => {}
+            Stage::Attack => {
+                let next = self.level as u32 + self.attack_rate as u32;
+                if next >= Self::FULL {
+                    self.level = Self::FULL as u16;
+                    self.stage = Stage::Decay;
+                } else {
+                    self.level = next as u16;
+                }
+            }
+            Stage::Decay => {
+                let floor = self.sustain_level as i32;
+                let next = self.level as i32 - self.decay_rate as i32;
+                if next <= floor {
+                    self.level = self.sustain_level;
+                    self.stage = Stage::Sustain;
+                } else {
+                    self.level = next as u16;
+                }
+            }
+            Stage::Sustain => {}
+            Stage::Release => {
+                let next = self.level as i32 - self.release_rate as i32;
+                if next <= 0 {
+                    self.level = 0;
+                    self.stage = Stage::Idle;
+                } else {
+                    self.level = next as u16;
+                }
+            }
+        }
+        self.level
+    }
+
+    /// Apply the current envelope level to a sample, in Q15 fixed point.
+    pub fn apply(&self, sample: i16) -> i16 {
+        ((sample as i32 * self.level as i32) >> 15) as i16
+    }
+}
+
+/// Resonant low-pass biquad, Direct Form I, per Robert Bristow-Johnson's
+/// Audio EQ Cookbook formulas.
+pub struct LowPassFilter {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl LowPassFilter {
+    pub fn new(cutoff_hz: f32, q: f32, sample_rate: u32) -> Self {
+        let mut filter = Self {
+            b0: 0.0,
+            b1: 0.0,
+            b2: 0.0,
+            a1: 0.0,
+            a2: 0.0,
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        };
+        filter.set_params(cutoff_hz, q, sample_rate);
+        filter
+    }
+
+    /// Recompute coefficients for a new cutoff/resonance, e.g. swept from the envelope.
+    ///
+    /// `cutoff_hz` is clamped below Nyquist: past `sample_rate / 2` the RBJ
+    /// formulas below aren't meaningful (`w0` wraps past `PI`), which a
+    /// cutoff sweep can easily hit at a low `sample_rate`.
+    pub fn set_params(&mut self, cutoff_hz: f32, q: f32, sample_rate: u32) {
+        let cutoff_hz = cutoff_hz.clamp(1.0, sample_rate as f32 * 0.49);
+        let w0 = 2.0 * core::f32::consts::PI * cutoff_hz / sample_rate as f32;
+        let (sin_w0, cos_w0) = (libm::sinf(w0), libm::cosf(w0));
+        let alpha = sin_w0 / (2.0 * q);
+
+        let b0 = (1.0 - cos_w0) / 2.0;
+        let b1 = 1.0 - cos_w0;
+        let b2 = b0;
+        let a0 = 1.0 + alpha;
+        let a1 = -2.0 * cos_w0;
+        let a2 = 1.0 - alpha;
+
+        self.b0 = b0 / a0;
+        self.b1 = b1 / a0;
+        self.b2 = b2 / a0;
+        self.a1 = a1 / a0;
+        self.a2 = a2 / a0;
+    }
+
+    pub fn process(&mut self, x: i16) -> i16 {
+        let x0 = x as f32;
+        let y = self.b0 * x0 + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+
+        self.x2 = self.x1;
+        self.x1 = x0;
+        self.y2 = self.y1;
+        self.y1 = y;
+
+        y.clamp(i16::MIN as f32, i16::MAX as f32) as i16
+    }
+}
+
+/// A single SID-style voice: oscillator -> ADSR -> resonant low-pass.
+pub struct Voice {
+    pub osc: Oscil,
+    pub envelope: Adsr,
+    pub filter: LowPassFilter,
+    base_cutoff_hz: f32,
+    cutoff_sweep_hz: f32,
+    q: f32,
+    sample_rate: u32,
+}
+
+impl Voice {
+    pub fn new(
+        osc: Oscil,
+        envelope: Adsr,
+        base_cutoff_hz: f32,
+        cutoff_sweep_hz: f32,
+        q: f32,
+        sample_rate: u32,
+    ) -> Self {
+        Self {
+            filter: LowPassFilter::new(base_cutoff_hz, q, sample_rate),
+            osc,
+            envelope,
+            base_cutoff_hz,
+            cutoff_sweep_hz,
+            q,
+            sample_rate,
+        }
+    }
+
+    pub fn gate(&mut self, on: bool) {
+        self.envelope.gate(on);
+    }
+
+    /// Advance the voice by one sample, sweeping the filter cutoff with the envelope.
+    pub fn next_sample(&mut self) -> i16 {
+        let env_level = self.envelope.next_level();
+        let cutoff = self.base_cutoff_hz
+            + self.cutoff_sweep_hz * (env_level as f32 / 0x7FFF as f32);
+        self.filter.set_params(cutoff, self.q, self.sample_rate);
+
+        let raw = self.osc.next_sample();
+        let shaped = self.envelope.apply(raw);
+        self.filter.process(shaped)
+    }
+}
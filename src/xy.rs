@@ -0,0 +1,39 @@
+//! Stereo XY / Lissajous vector mode.
+//!
+//! `Data16Channel16` carries independent left/right samples per frame; this
+//! module treats them as the X/Y axes of a scope in XY mode so closed
+//! parametric curves (circles, Lissajous figures, the heart outline) render
+//! correctly instead of just looking like voltage-over-time traces.
+
+/// A buffer of paired (x, y) samples, one pair per I2S frame.
+pub struct XyPattern<const N: usize> {
+    pub x: [i16; N],
+    pub y: [i16; N],
+}
+
+impl<const N: usize> XyPattern<N> {
+    /// Generate a Lissajous curve `x = sin(a*t), y = sin(b*t + delta)` over
+    /// one full parametric cycle, `a`/`b` being the frequency ratio and
+    /// `delta_rad` the phase offset between axes.
+    pub fn lissajous(freq_ratio_a: f32, freq_ratio_b: f32, delta_rad: f32) -> Self {
+        let mut x = [0i16; N];
+        let mut y = [0i16; N];
+
+        for i in 0..N {
+            let t = 2.0 * core::f32::consts::PI * i as f32 / N as f32;
+            x[i] = (libm::sinf(freq_ratio_a * t) * 32767.0) as i16;
+            y[i] = (libm::sinf(freq_ratio_b * t + delta_rad) * 32767.0) as i16;
+        }
+
+        Self { x, y }
+    }
+
+    /// Pack the paired samples into an interleaved L/R DMA buffer
+    /// (`[x0, y0, x1, y1, ...]`), scaled into the I2S `u16` range.
+    pub fn interleave_into(&self, dest: &mut [u16]) {
+        for i in 0..N {
+            dest[2 * i] = (self.x[i] as i32 + 32768) as u16;
+            dest[2 * i + 1] = (self.y[i] as i32 + 32768) as u16;
+        }
+    }
+}